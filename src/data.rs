@@ -0,0 +1,107 @@
+use crate::number::Native;
+pub use crate::number::Number;
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A vote count, seat count, or any other quantity measured in whole units.
+pub type Count = u64;
+
+/// The number of votes cast for a single party.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Votes(pub Count);
+
+/// A party's seats, or the pool of seats still available to hand out.
+///
+/// Both roles share this type: `transfer` moves a single seat out of
+/// whichever `Seats` value currently represents the pool and into whichever
+/// represents the recipient.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Seats {
+    count: Count,
+    limit: Option<Count>,
+}
+
+impl Seats {
+    /// A pool of `count` seats, ready to be handed out.
+    pub fn filled(count: Count) -> Seats {
+        Seats { count: count, limit: None }
+    }
+
+    /// A party that can be awarded at most `limit` seats (its candidate list
+    /// is shorter than its possible allocation).
+    pub fn limited(limit: Count) -> Seats {
+        Seats { count: 0, limit: Some(limit) }
+    }
+
+    /// A party with no cap on the number of seats it can be awarded.
+    pub fn unlimited() -> Seats {
+        Seats { count: 0, limit: None }
+    }
+
+    pub fn count(&self) -> Count {
+        self.count
+    }
+
+    pub fn has_candidates(&self) -> bool {
+        self.limit.map_or(true, |limit| self.count < limit)
+    }
+
+    /// Move a single seat from `other` to `self`.
+    pub fn transfer(&mut self, other: &mut Seats) {
+        self.count += 1;
+        other.count -= 1;
+    }
+}
+
+impl fmt::Display for Seats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.count)
+    }
+}
+
+/// The largest-average/largest-surplus figure used to rank parties, kept as
+/// an exact ratio `numerator/denominator` rather than a lossy float, and
+/// compared by cross-multiplication in whatever precision `N` provides.
+#[derive(Clone, Copy, Debug)]
+pub struct Quality<N: Number = Native> {
+    numerator: Count,
+    denominator: Count,
+    backend: PhantomData<N>,
+}
+
+impl<N: Number> PartialEq for Quality<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N: Number> Eq for Quality<N> {}
+
+impl<N: Number> PartialOrd for Quality<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: Number> Ord for Quality<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        N::compare_ratio(self.numerator, self.denominator, other.numerator, other.denominator)
+    }
+}
+
+impl<N: Number> Quality<N> {
+    /// This quality's value as an exact `numerator/denominator` pair, for
+    /// reporting (see [`crate::trace::Stage`]).
+    pub fn as_ratio(&self) -> (Count, Count) {
+        (self.numerator, self.denominator)
+    }
+}
+
+/// Construct the ratio `a/b` used to rank parties against each other,
+/// compared exactly by cross-multiplication in the precision the backend
+/// `N` provides (see [`Number`]).
+pub fn frac<N: Number>(a: Count, b: Count) -> Quality<N> {
+    Quality { numerator: a, denominator: b, backend: PhantomData }
+}
+
@@ -1,14 +1,27 @@
 mod data;
+mod method;
+mod number;
+mod tiebreak;
+mod trace;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 pub use data::*;
+pub use method::{DivisorMethod, Method, QuotaMethod};
+pub use number::{Native, Number, Rational};
+pub use tiebreak::{Draw, TieBreak};
+pub use trace::{AllocationLog, Phase, Ratio, Stage};
 use std::iter;
 
-pub fn allocate_single_step<Quality: Ord>(
+pub fn allocate_single_step<Quality: Ord + Ratio>(
     votes: &[Votes],
     seats: &mut [Seats],
     available_seats: &mut Seats,
     criterion: impl Fn(Votes, Seats) -> Option<Quality>,
-) -> Option<()> {
+    prev_seats: &[Seats],
+    tie_break: &TieBreak,
+    phase: Phase,
+) -> Option<AllocationLog> {
     let qualities = iter::zip(votes, seats.iter())
         .map(|(votes, seats)| {
             if seats.has_candidates() {
@@ -21,18 +34,39 @@ pub fn allocate_single_step<Quality: Ord>(
 
     let max_quality = qualities.iter().max().unwrap().as_ref()?;
 
-    let awarded = iter::zip(qualities.iter(), seats)
-        .filter_map(|(quality, seat)| (quality.as_ref() == Some(max_quality)).then_some(seat))
+    let tied = (0..seats.len())
+        .zip(qualities.iter())
+        .zip(seats.iter_mut())
+        .zip(prev_seats)
+        .filter_map(|(((index, quality), seat), prev)| {
+            (quality.as_ref() == Some(max_quality)).then_some(((index, seat), *prev))
+        })
         .collect::<Vec<_>>();
 
-    for seat in ballotted(awarded, available_seats.count()) {
+    let (awarded, lots) = tie_break.resolve(tied, available_seats.count());
+    let mut winners = Vec::new();
+    for (index, seat) in awarded {
         seat.transfer(available_seats);
+        winners.push(index);
     }
 
-    Some(())
+    let stage = Stage {
+        phase,
+        qualities: qualities.iter().map(|q| q.as_ref().map(Ratio::as_ratio)).collect(),
+        winners,
+        lot: !lots.is_empty(),
+        totals: seats.iter().map(Seats::count).collect(),
+    };
+
+    Some(AllocationLog { lots, trace: vec![stage] })
 }
 
-pub fn absolute_majority_check(votes: &[Votes], seats: &mut [Seats], prev_seats: Vec<Seats>) {
+pub fn absolute_majority_check(
+    votes: &[Votes],
+    seats: &mut [Seats],
+    prev_seats: Vec<Seats>,
+    tie_break: &TieBreak,
+) -> AllocationLog {
     let total_votes = votes.iter().map(|Votes(count)| count).sum::<Count>();
     let total_seats = seats.iter().map(|count| count.count()).sum::<Count>();
 
@@ -40,33 +74,53 @@ pub fn absolute_majority_check(votes: &[Votes], seats: &mut [Seats], prev_seats:
 
     let absolute_majority = |count, total| 2 * count > total;
 
-    if let Some((_, winner_seat)) =
-        iter::zip(votes, seats.iter_mut()).find(|(Votes(cur_vote), cur_seat)| {
+    if let Some((winner_index, winner_seat)) = iter::zip(votes, seats.iter_mut())
+        .enumerate()
+        .find(|(_, (Votes(cur_vote), cur_seat))| {
             cur_seat.has_candidates()
                 && absolute_majority(*cur_vote, total_votes)
                 && !absolute_majority(cur_seat.count(), total_seats)
         })
+        .map(|(index, (_, seat))| (index, seat))
     {
         #[cfg(feature = "chatty")]
         eprintln!("an absolute majority correction was performed");
         winner_seat.transfer(&mut correction);
         let winner_seat = *winner_seat;
 
-        let last_winners = iter::zip(seats.iter_mut(), prev_seats)
-            .filter_map(|(x, y)| (*x > y && *x != winner_seat).then_some(x))
+        let contenders = (0..seats.len())
+            .zip(seats.iter())
+            .zip(prev_seats)
+            .filter_map(|((index, x), y)| {
+                (*x > y && *x != winner_seat && index != winner_index).then_some((index, y))
+            })
             .collect::<Vec<_>>();
 
-        let loser_seat = ballotted(last_winners, 1).next().unwrap();
-        correction.transfer(loser_seat);
+        let indices = contenders.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        let keep = (contenders.len() - 1) as Count;
+        let (keepers, log) = tie_break.resolve(contenders, keep);
+        let loser_index = indices.into_iter().find(|index| !keepers.contains(index)).unwrap();
+        correction.transfer(&mut seats[loser_index]);
+
+        let stage = Stage {
+            phase: Phase::MajorityCorrection,
+            qualities: vec![None; seats.len()],
+            winners: vec![winner_index],
+            lot: !log.is_empty(),
+            totals: seats.iter().map(Seats::count).collect(),
+        };
+
+        return AllocationLog { lots: log, trace: vec![stage] };
     }
+
+    AllocationLog::default()
 }
 
-#[cfg(feature = "chatty")]
-pub fn whole_seats_available(votes: &[Votes], seats: &[Seats], seats_awarded: Seats) -> bool {
+pub fn whole_seats_available<N: Number>(votes: &[Votes], seats: &[Seats], seats_awarded: Seats) -> bool {
     let total_seats = seats_awarded.count() + seats.iter().map(|x| x.count()).sum::<Count>();
     let total_votes = votes.iter().map(|Votes(x)| x).sum::<Count>();
     iter::zip(votes, seats).any(|(Votes(cur_vote), cur_seat)| {
-        frac(*cur_vote, cur_seat.count() + 1) >= frac(total_votes, total_seats)
+        frac::<N>(*cur_vote, cur_seat.count() + 1) >= frac::<N>(total_votes, total_seats)
     })
 }
 
@@ -78,102 +132,197 @@ fn debug_results<'a>(seats: impl Iterator<Item = &'a Seats>) {
     eprintln!();
 }
 
-pub fn allocate_seats<Quality: Ord>(
+pub fn allocate_seats<N: Number, Quality: Ord + Ratio>(
     votes: &[Votes],
     seats: &mut [Seats],
     available_seats: &mut Seats,
     method: impl Fn(Votes, Seats) -> Option<Quality> + Copy,
-) {
+    tie_break: &TieBreak,
+) -> AllocationLog {
     let mut last_winners = seats.to_owned();
+    let mut log = AllocationLog::default();
     #[cfg(feature = "chatty")]
     let mut printed = false;
     while available_seats.count() > 0 {
+        let full_seats_available = whole_seats_available::<N>(votes, seats, *available_seats);
         #[cfg(feature = "chatty")]
-        if !(whole_seats_available(votes, seats, *available_seats) || printed) {
+        if !(full_seats_available || printed) {
             printed = true;
             eprintln!("rest seats");
         }
+        let phase = if full_seats_available { Phase::FullSeat } else { Phase::RestSeat };
 
         last_winners.copy_from_slice(seats);
 
-        if allocate_single_step(votes, seats, available_seats, method).is_none() {
-            return;
+        match allocate_single_step(votes, seats, available_seats, method, &last_winners, tie_break, phase)
+        {
+            Some(step_log) => log.extend(step_log),
+            None => return log,
         }
 
         #[cfg(feature = "chatty")]
         debug_results(seats.iter());
     }
 
-    absolute_majority_check(votes, seats, last_winners);
+    log.extend(absolute_majority_check(votes, seats, last_winners, tie_break));
+    log
 }
 
-pub fn allocate_per_average(mut total_seats: Seats, votes: Vec<Votes>, seats: &mut [Seats]) {
-    allocate_seats(
+pub fn allocate_per_average<N: Number>(
+    method: DivisorMethod,
+    mut total_seats: Seats,
+    votes: Vec<Votes>,
+    seats: &mut [Seats],
+    tie_break: &TieBreak,
+) -> AllocationLog {
+    allocate_seats::<N, _>(
         &votes,
         seats,
         &mut total_seats,
-        |Votes(cur_vote), cur_seat| Some(frac(cur_vote, cur_seat.count() + 1)),
-    );
+        move |Votes(cur_vote), cur_seat| Some(method.quality::<N>(cur_vote, cur_seat.count())),
+        tie_break,
+    )
 }
 
-pub fn allocate_per_surplus(mut total_seats: Seats, votes: Vec<Votes>, seats: &mut [Seats]) {
+/// Dutch largest-surplus allocation: the surplus-detection criterion itself
+/// is D'Hondt-specific, so callers should only reach this for
+/// [`DivisorMethod::DHondt`]; the "continuing by averages" tail phase
+/// still uses the given `method` for consistency.
+pub fn allocate_per_surplus<N: Number>(
+    method: DivisorMethod,
+    mut total_seats: Seats,
+    votes: Vec<Votes>,
+    seats: &mut [Seats],
+    tie_break: &TieBreak,
+) -> AllocationLog {
     let vote_count = votes.iter().map(|Votes(count)| count).sum::<Count>();
     let seat_count = total_seats.count();
 
-    let has_surplus =
-        |cur_vote, cur_seat| frac(cur_vote, 1) >= frac(cur_seat * vote_count, seat_count);
+    let has_surplus = |cur_vote, cur_seat| {
+        frac::<N>(cur_vote, 1) >= frac::<N>(cur_seat * vote_count, seat_count)
+    };
 
-    allocate_seats(
+    let mut log = allocate_seats::<N, _>(
         &votes,
         seats,
         &mut total_seats,
         move |Votes(cur_vote), cur_seat| {
             let cur_seat = cur_seat.count();
             (has_surplus(cur_vote, cur_seat)
-                && frac(cur_vote, 1) >= frac(3 * vote_count, 4 * seat_count))
-            .then(|| cur_vote * seat_count - cur_seat * vote_count)
+                && frac::<N>(cur_vote, 1) >= frac::<N>(3 * vote_count, 4 * seat_count))
+            .then(|| frac::<N>(cur_vote * seat_count - cur_seat * vote_count, 1))
         },
+        tie_break,
     );
 
     if total_seats.count() > 0 {
         #[cfg(feature = "chatty")]
         eprintln!("continuing by averages");
-        allocate_seats(
+        log.extend(allocate_seats::<N, _>(
             &votes,
             seats,
             &mut total_seats,
-            |Votes(cur_vote), cur_seat| {
+            move |Votes(cur_vote), cur_seat| {
                 let cur_seat = cur_seat.count();
-                if frac(cur_vote, 1) >= frac(3 * vote_count, 4 * seat_count) {
+                if frac::<N>(cur_vote, 1) >= frac::<N>(3 * vote_count, 4 * seat_count) {
                     has_surplus(cur_vote, cur_seat - 1)
                 } else {
                     has_surplus(cur_vote, cur_seat)
                 }
-                .then_some(frac(cur_vote, cur_seat + 1))
+                .then_some(method.quality::<N>(cur_vote, cur_seat))
             },
-        );
+            tie_break,
+        ));
     }
+
+    log
 }
 
-pub fn allocate(total_seats: Seats, votes: Vec<Votes>, seats: &mut [Seats]) {
-    if total_seats.count() >= 19 {
-        allocate_per_average(total_seats, votes, seats);
-    } else {
-        allocate_per_surplus(total_seats, votes, seats);
+/// Largest-remainder allocation: award whole seats for each multiple of
+/// `method`'s quota outright, then hand out the rest one at a time to
+/// whoever has the largest remainder (via the usual `allocate_seats` loop).
+pub fn allocate_by_quota<N: Number>(
+    method: QuotaMethod,
+    mut total_seats: Seats,
+    votes: Vec<Votes>,
+    seats: &mut [Seats],
+    tie_break: &TieBreak,
+) -> AllocationLog {
+    let vote_count = votes.iter().map(|Votes(count)| count).sum::<Count>();
+    let seat_count = total_seats.count();
+    let (quota_num, quota_den) = method.quota(vote_count, seat_count);
+
+    for (Votes(cur_vote), seat) in iter::zip(&votes, seats.iter_mut()) {
+        let mut whole = (cur_vote * quota_den / quota_num).min(total_seats.count());
+        while whole > 0 && seat.has_candidates() {
+            seat.transfer(&mut total_seats);
+            whole -= 1;
+        }
     }
+
+    // Each party's remainder, `cur_vote - cur_seat * quota`, kept as the
+    // exact fraction `(cur_vote * quota_den - cur_seat * quota_num) /
+    // quota_den` rather than rounding the quota down first -- otherwise a
+    // party with more whole seats than its true quota entitles could be
+    // pulled into contention for the rest seats it has no claim to.
+    allocate_seats::<N, _>(
+        &votes,
+        seats,
+        &mut total_seats,
+        move |Votes(cur_vote), cur_seat| {
+            let remainder = (cur_vote * quota_den).saturating_sub(cur_seat.count() * quota_num);
+            Some(frac::<N>(remainder, quota_den))
+        },
+        tie_break,
+    )
+}
+
+/// Run an allocation using the given [`Method`], picking between
+/// largest-averages (via the Dutch largest-surplus rule, below 19 seats,
+/// but only for [`DivisorMethod::DHondt`] -- the surplus criterion itself
+/// is D'Hondt-specific) and largest-remainder as appropriate.
+pub fn allocate_method<N: Number>(
+    method: Method,
+    total_seats: Seats,
+    votes: Vec<Votes>,
+    seats: &mut [Seats],
+    tie_break: &TieBreak,
+) -> AllocationLog {
+    match method {
+        Method::Quota(method) => allocate_by_quota::<N>(method, total_seats, votes, seats, tie_break),
+        Method::Divisor(method @ DivisorMethod::DHondt) if total_seats.count() < 19 => {
+            allocate_per_surplus::<N>(method, total_seats, votes, seats, tie_break)
+        }
+        Method::Divisor(method) => allocate_per_average::<N>(method, total_seats, votes, seats, tie_break),
+    }
+}
+
+pub fn allocate<N: Number>(
+    total_seats: Seats,
+    votes: Vec<Votes>,
+    seats: &mut [Seats],
+    tie_break: &TieBreak,
+) -> AllocationLog {
+    allocate_method::<N>(Method::default(), total_seats, votes, seats, tie_break)
 }
 
-pub fn allocate_national(mut total_seats: Seats, votes: Vec<Votes>, seats: &mut [Seats]) {
+pub fn allocate_national<N: Number>(
+    mut total_seats: Seats,
+    votes: Vec<Votes>,
+    seats: &mut [Seats],
+    tie_break: &TieBreak,
+) -> AllocationLog {
     let vote_count = votes.iter().map(|Votes(count)| count).sum::<Count>();
     let seat_count = total_seats.count();
 
-    allocate_seats(
+    allocate_seats::<N, _>(
         &votes,
         seats,
         &mut total_seats,
         |Votes(cur_vote), cur_seat| {
-            (frac(cur_vote, 1) >= frac(vote_count, seat_count))
-                .then_some(frac(cur_vote, cur_seat.count() + 1))
+            (frac::<N>(cur_vote, 1) >= frac::<N>(vote_count, seat_count))
+                .then_some(frac::<N>(cur_vote, cur_seat.count() + 1))
         },
-    );
+        tie_break,
+    )
 }
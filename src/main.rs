@@ -19,7 +19,49 @@ enum Command {
     Allocate(AllocateArgs),
     /// Validate election results from CSV file(s)
     #[cfg(feature = "validate")]
-    Validate { files: Vec<PathBuf> },
+    Validate {
+        files: Vec<PathBuf>,
+        /// Use exact (arbitrary-precision) arithmetic when comparing quality
+        /// ratios; the vote and seat products fed into those comparisons are
+        /// still computed in u64
+        #[arg(short, long)]
+        exact: bool,
+        /// Seed for the reproducible drawing of lots
+        #[arg(long, default_value = "electionchecker")]
+        seed: String,
+        /// Break ties by the previous step's standings before falling back
+        /// to the seeded lottery
+        #[arg(long)]
+        countback: bool,
+        /// Divisor/quota method to check the apportionment against
+        #[arg(short, long, value_enum)]
+        method: Option<MethodArg>,
+    },
+}
+
+/// The divisor/quota methods selectable on the command line; see
+/// [`kiesraad_model::Method`] for what each one does.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum MethodArg {
+    DHondt,
+    SainteLague,
+    ModifiedSainteLague,
+    Hare,
+    Droop,
+    Imperiali,
+}
+
+impl From<MethodArg> for Method {
+    fn from(value: MethodArg) -> Method {
+        match value {
+            MethodArg::DHondt => Method::Divisor(DivisorMethod::DHondt),
+            MethodArg::SainteLague => Method::Divisor(DivisorMethod::SainteLague),
+            MethodArg::ModifiedSainteLague => Method::Divisor(DivisorMethod::ModifiedSainteLague),
+            MethodArg::Hare => Method::Quota(QuotaMethod::Hare),
+            MethodArg::Droop => Method::Quota(QuotaMethod::Droop),
+            MethodArg::Imperiali => Method::Quota(QuotaMethod::Imperiali),
+        }
+    }
 }
 
 #[derive(Args)]
@@ -37,6 +79,45 @@ struct AllocateArgs {
     /// Use a voting threshold of one whole seat, as used in Dutch national elections
     #[arg(short, long)]
     national: bool,
+    /// Use exact (arbitrary-precision) arithmetic instead of widened native
+    /// integers
+    #[arg(short, long)]
+    exact: bool,
+    /// Seed for the reproducible drawing of lots, so a contested result can
+    /// be independently re-verified
+    #[arg(long, default_value = "electionchecker")]
+    seed: String,
+    /// Break ties by the previous step's standings before falling back to
+    /// the seeded lottery
+    #[arg(long)]
+    countback: bool,
+    /// Print the per-stage allocation trace as JSON, for independent audit
+    #[cfg(feature = "serde")]
+    #[arg(long)]
+    trace: bool,
+    /// Divisor/quota method to use; ignored when --national is given
+    #[arg(short, long, value_enum)]
+    method: Option<MethodArg>,
+}
+
+fn tie_break(seed: &str, countback: bool) -> TieBreak {
+    let seed = seed.to_owned();
+    if countback {
+        TieBreak::Countback { seed }
+    } else {
+        TieBreak::Lottery { seed }
+    }
+}
+
+fn print_lots(lots: &[Draw]) {
+    for lot in lots {
+        println!("drew lot #{} -> index {}", lot.counter, lot.index);
+    }
+}
+
+#[cfg(feature = "serde")]
+fn print_trace(trace: &[Stage]) {
+    println!("{}", serde_json::to_string_pretty(trace).unwrap());
 }
 
 fn main() {
@@ -70,17 +151,31 @@ under certain conditions, see the file LICENSE
             } else {
                 vec![Seats::unlimited(); votes.len()]
             };
-            if args.national {
-                allocate_national(Seats::filled(args.seats), &votes, &mut seats);
-            } else {
-                allocate(Seats::filled(args.seats), &votes, &mut seats);
-            }
+            let tie_break = tie_break(&args.seed, args.countback);
+            let method = args.method.map(Method::from).unwrap_or_default();
+            let log = match (args.national, args.exact) {
+                (true, true) => allocate_national::<Rational>(Seats::filled(args.seats), votes, &mut seats, &tie_break),
+                (true, false) => allocate_national::<Native>(Seats::filled(args.seats), votes, &mut seats, &tie_break),
+                (false, true) => allocate_method::<Rational>(method, Seats::filled(args.seats), votes, &mut seats, &tie_break),
+                (false, false) => allocate_method::<Native>(method, Seats::filled(args.seats), votes, &mut seats, &tie_break),
+            };
             print_seats(seats.into_iter());
+            print_lots(&log.lots);
+            #[cfg(feature = "serde")]
+            if args.trace {
+                print_trace(&log.trace);
+            }
         }
         #[cfg(feature = "validate")]
-        Command::Validate { files } => {
+        Command::Validate { files, exact, seed, countback, method } => {
             println!("Validating {} files...", files.len());
-            validate(files);
+            let tie_break = tie_break(seed, *countback);
+            let method = method.map(Method::from).unwrap_or_default();
+            if *exact {
+                validate::<Rational>(files, &tie_break, method);
+            } else {
+                validate::<Native>(files, &tie_break, method);
+            }
         }
     }
 }
@@ -94,13 +189,15 @@ fn print_seats(seats: impl Iterator<Item = Seats>) {
 }
 
 fn demo() {
+    let tie_break = TieBreak::Lottery { seed: "electionchecker demo".to_owned() };
+
     macro_rules! votes {
     ($($x: expr),* $(,)?) => {
         vec![$(Votes($x),)*]
     }
     }
 
-    fn run_election(target: Count, votes: Vec<Votes>) {
+    let run_election = |target: Count, votes: Vec<Votes>| {
         println!(
             "running an election for {target} seats, parties: {votes:?}, using largest {}",
             if target >= 19 {
@@ -110,10 +207,10 @@ fn demo() {
             }
         );
         let mut seats = vec![Seats::unlimited(); votes.len()];
-        allocate(Seats::filled(target), &votes, &mut seats);
+        allocate::<Native>(Seats::filled(target), votes, &mut seats, &tie_break);
         print_seats(seats.into_iter());
         println!("======");
-    }
+    };
 
     run_election(19, votes![40, 30, 20, 10]);
     run_election(24, votes![21, 20]);
@@ -124,13 +221,13 @@ fn demo() {
     run_election(5, votes![19, 19, 19, 19, 15, 9, 9]);
     run_election(18, votes![100, 16, 6, 5, 5, 5, 5, 4]);
 
-    fn run_national_election(votes: Vec<Votes>) {
+    let run_national_election = |votes: Vec<Votes>| {
         println!("running an election for Tweede Kamer");
         let mut seats = vec![Seats::unlimited(); votes.len()];
-        allocate_national(Seats::filled(150), &votes, &mut seats);
+        allocate_national::<Native>(Seats::filled(150), votes, &mut seats, &tie_break);
         print_seats(seats.into_iter());
         println!("======");
-    }
+    };
 
     #[rustfmt::skip]
     run_national_election(votes![
@@ -165,18 +262,18 @@ fn demo() {
     println!("a corner case in our national voting system");
     let votes = votes![33, 7];
     let mut seats = vec![Seats::limited(2), Seats::limited(13)];
-    allocate(Seats::filled(4), &votes, &mut seats);
+    allocate::<Native>(Seats::filled(4), votes, &mut seats, &tie_break);
     print_seats(seats.into_iter());
 
     println!("a weird consequence of a little sentence in the law");
     let votes = votes![33, 7, 0];
     let mut seats = vec![Seats::limited(2), Seats::limited(12), Seats::limited(2)];
-    allocate(Seats::filled(4), &votes, &mut seats);
+    allocate::<Native>(Seats::filled(4), votes, &mut seats, &tie_break);
     print_seats(seats.into_iter());
 }
 
 #[cfg(feature = "validate")]
-fn validate(data_sources: &Vec<PathBuf>) {
+fn validate<N: Number>(data_sources: &Vec<PathBuf>, tie_break: &TieBreak, method: Method) {
     for data_source in data_sources {
         let records = csv::ReaderBuilder::new()
             .has_headers(true)
@@ -243,15 +340,15 @@ fn validate(data_sources: &Vec<PathBuf>) {
             let file_name = data_source.file_name().unwrap().to_string_lossy();
             if file_name.starts_with("uitslag_TK") || file_name.starts_with("uitslag_EP") {
                 match &file_name[10..14] {
-                    "1918" => allocate_1918(Seats::filled(total_seats), votes, &mut seats),
-                    "1922" => allocate_1922(Seats::filled(total_seats), votes, &mut seats),
+                    "1918" => allocate_1918::<N>(Seats::filled(total_seats), votes, &mut seats, tie_break),
+                    "1922" => allocate_1922::<N>(Seats::filled(total_seats), votes, &mut seats, tie_break),
                     "1925" | "1929" | "1933" => {
-                        allocate_bongaerts(Seats::filled(total_seats), votes, &mut seats)
+                        allocate_bongaerts::<N>(Seats::filled(total_seats), votes, &mut seats, tie_break)
                     }
-                    _ => allocate_national(Seats::filled(total_seats), votes, &mut seats),
+                    _ => allocate_national::<N>(Seats::filled(total_seats), votes, &mut seats, tie_break),
                 }
             } else {
-                allocate(Seats::filled(total_seats), votes, &mut seats);
+                allocate_method::<N>(method, Seats::filled(total_seats), votes, &mut seats, tie_break);
             }
 
             assert_eq!(
@@ -0,0 +1,105 @@
+use crate::{frac, Count, Number, Quality};
+
+/// Which divisor sequence ranks parties against each other in a
+/// largest-averages allocation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DivisorMethod {
+    /// Divisors 1, 2, 3, ...
+    #[default]
+    DHondt,
+    /// Divisors 1, 3, 5, ...
+    SainteLague,
+    /// Sainte-Laguë with a first divisor of 1.4 instead of 1, to make a
+    /// party's first seat slightly harder to win.
+    ModifiedSainteLague,
+}
+
+impl DivisorMethod {
+    /// The divisor for a party that already holds `cur_seat` seats,
+    /// expressed as an exact `numerator/denominator` pair.
+    fn divisor(self, cur_seat: Count) -> (Count, Count) {
+        match self {
+            DivisorMethod::DHondt => (cur_seat + 1, 1),
+            DivisorMethod::SainteLague => (2 * cur_seat + 1, 1),
+            DivisorMethod::ModifiedSainteLague if cur_seat == 0 => (7, 5),
+            DivisorMethod::ModifiedSainteLague => (2 * cur_seat + 1, 1),
+        }
+    }
+
+    /// A party's quality this round: `votes / divisor(cur_seat)`.
+    pub fn quality<N: Number>(self, cur_vote: Count, cur_seat: Count) -> Quality<N> {
+        let (divisor, scale) = self.divisor(cur_seat);
+        frac::<N>(cur_vote * scale, divisor)
+    }
+}
+
+/// Which quota a party must reach per whole seat in a largest-remainder
+/// allocation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum QuotaMethod {
+    /// `votes / seats`.
+    #[default]
+    Hare,
+    /// `votes / (seats + 1)`.
+    Droop,
+    /// `votes / (seats + 2)`.
+    Imperiali,
+}
+
+impl QuotaMethod {
+    /// The quota a party must reach per whole seat, expressed as an exact
+    /// `numerator/denominator` pair.
+    pub fn quota(self, vote_count: Count, seat_count: Count) -> (Count, Count) {
+        match self {
+            QuotaMethod::Hare => (vote_count, seat_count),
+            QuotaMethod::Droop => (vote_count, seat_count + 1),
+            QuotaMethod::Imperiali => (vote_count, seat_count + 2),
+        }
+    }
+}
+
+/// Which divisor/quota method ranks parties against each other.
+///
+/// [`Method::Divisor(DivisorMethod::DHondt)`](DivisorMethod::DHondt) is the
+/// Dutch default; the rest let the same engine validate apportionments from
+/// other jurisdictions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Largest-averages: award seats one at a time by largest average (via
+    /// [`DivisorMethod::quality`]).
+    Divisor(DivisorMethod),
+    /// Largest-remainder: award whole seats by quota, then the rest by
+    /// largest remainder (via [`QuotaMethod::quota`]).
+    Quota(QuotaMethod),
+}
+
+impl Default for Method {
+    fn default() -> Method {
+        Method::Divisor(DivisorMethod::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Native;
+
+    #[test]
+    fn dhondt_divisors_are_whole_numbers() {
+        assert_eq!(DivisorMethod::DHondt.quality::<Native>(100, 0), frac(100, 1));
+        assert_eq!(DivisorMethod::DHondt.quality::<Native>(100, 1), frac(100, 2));
+    }
+
+    #[test]
+    fn modified_sainte_lague_raises_the_first_divisor() {
+        assert_eq!(DivisorMethod::ModifiedSainteLague.quality::<Native>(7, 0), frac(5, 1));
+        assert_eq!(DivisorMethod::SainteLague.quality::<Native>(7, 0), frac(7, 1));
+    }
+
+    #[test]
+    fn quota_methods_use_seat_count_plus_offset() {
+        assert_eq!(QuotaMethod::Hare.quota(100, 10), (100, 10));
+        assert_eq!(QuotaMethod::Droop.quota(100, 10), (100, 11));
+        assert_eq!(QuotaMethod::Imperiali.quota(100, 10), (100, 12));
+    }
+}
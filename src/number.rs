@@ -0,0 +1,61 @@
+use crate::Count;
+use std::cmp::Ordering;
+
+/// Arithmetic backend used to compare the quality ratios that rank parties
+/// against each other.
+///
+/// `allocate_seats` and friends are generic over this trait so the same
+/// largest-averages/largest-surplus logic can run on a fast native backend
+/// or, for elections whose vote totals might otherwise overflow, on an
+/// arbitrary-precision one. See [`Native`] and [`Rational`].
+pub trait Number: Copy {
+    /// Compare the ratio `a1/b1` against `a2/b2` by the sign of
+    /// `a1*b2 - a2*b1`. [`Rational`] does so without overflow for inputs of
+    /// any magnitude; [`Native`] overflows once a product exceeds `i128::MAX`.
+    fn compare_ratio(a1: Count, b1: Count, a2: Count, b2: Count) -> Ordering;
+}
+
+/// Fast path: widen to `i128` before multiplying. Correct for any election
+/// whose vote and seat counts plausibly fit in a `Count` (`u64`), which is
+/// every real-world case, but not one chosen adversarially to overflow even
+/// that.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Native;
+
+impl Number for Native {
+    fn compare_ratio(a1: Count, b1: Count, a2: Count, b2: Count) -> Ordering {
+        (a1 as i128 * b2 as i128).cmp(&(a2 as i128 * b1 as i128))
+    }
+}
+
+/// Exact path: arbitrary-precision integers, so results are provably
+/// correct for any magnitude of input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rational;
+
+impl Number for Rational {
+    fn compare_ratio(a1: Count, b1: Count, a2: Count, b2: Count) -> Ordering {
+        use num_bigint::BigInt;
+        (BigInt::from(a1) * BigInt::from(b2)).cmp(&(BigInt::from(a2) * BigInt::from(b1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_and_rational_agree() {
+        let cases = [(1, 2, 1, 3), (5, 7, 5, 7), (100, 3, 99, 3), (0, 1, 0, 5)];
+        for (a1, b1, a2, b2) in cases {
+            assert_eq!(Native::compare_ratio(a1, b1, a2, b2), Rational::compare_ratio(a1, b1, a2, b2));
+        }
+    }
+
+    #[test]
+    fn compare_ratio_orders_by_value() {
+        assert_eq!(Native::compare_ratio(1, 2, 1, 3), Ordering::Greater);
+        assert_eq!(Native::compare_ratio(1, 3, 1, 2), Ordering::Less);
+        assert_eq!(Native::compare_ratio(2, 4, 1, 2), Ordering::Equal);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Count, Seats};
+use sha2::{Digest, Sha256};
+
+/// How a tie between equally-ranked parties is resolved when there are
+/// fewer remaining seats than tied candidates.
+///
+/// Dutch law requires drawing lots (`loting`) in this case; both variants
+/// keep that reproducible by deriving every draw from a user-supplied seed
+/// rather than from system randomness.
+#[derive(Clone, Debug)]
+pub enum TieBreak {
+    /// Draw lots straight away.
+    Lottery { seed: String },
+    /// Prefer whichever party held more seats after the previous step
+    /// (`loting bij voorkeur van de stand bij de vorige zetelverdeling`),
+    /// falling back to the seeded lottery for any candidates still tied.
+    Countback { seed: String },
+}
+
+/// A single recorded drawing-of-lots: which draw in the sequence it was and
+/// which candidate index (counted from the remaining pool at that point) it
+/// selected, so the draw can be printed and reproduced later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(any(feature = "serde", feature = "wasm"), derive(serde::Serialize))]
+pub struct Draw {
+    pub counter: u64,
+    pub index: usize,
+}
+
+impl TieBreak {
+    fn seed(&self) -> &str {
+        match self {
+            TieBreak::Lottery { seed } | TieBreak::Countback { seed } => seed,
+        }
+    }
+
+    /// Pick winners for `available` seats out of the `tied` candidates
+    /// (each paired with its seat count after the previous step), using
+    /// that previous standing for [`TieBreak::Countback`] before falling
+    /// back to the seeded lottery, and returning the log of any lots that
+    /// were drawn alongside the winners.
+    pub fn resolve<T>(&self, tied: Vec<(T, Seats)>, available: Count) -> (Vec<T>, Vec<Draw>) {
+        let available = available as usize;
+        if tied.len() <= available {
+            return (tied.into_iter().map(|(seat, _)| seat).collect(), Vec::new());
+        }
+
+        if !matches!(self, TieBreak::Countback { .. }) {
+            let (lots, log) = self.draw(tied, available as Count);
+            return (lots.into_iter().map(|(seat, _)| seat).collect(), log);
+        }
+
+        let mut ranked = tied;
+        ranked.sort_by_key(|(_, prev)| std::cmp::Reverse(prev.count()));
+
+        let cutoff = ranked[available - 1].1.count();
+        let secure = ranked.partition_point(|(_, prev)| prev.count() > cutoff);
+        // Anyone strictly below cutoff never competes for a remaining seat at
+        // all; only those tied exactly at cutoff go into the lottery pool.
+        let tied_at_cutoff = ranked.partition_point(|(_, prev)| prev.count() >= cutoff);
+        ranked.truncate(tied_at_cutoff);
+        let contested = ranked.split_off(secure);
+        let mut winners = ranked.into_iter().map(|(seat, _)| seat).collect::<Vec<_>>();
+
+        let still_needed = (available - winners.len()) as Count;
+        if contested.len() as Count <= still_needed {
+            // Exactly as many candidates are tied at cutoff as there are
+            // seats left -- everyone wins outright, no lot drawn.
+            winners.extend(contested.into_iter().map(|(seat, _)| seat));
+            return (winners, Vec::new());
+        }
+
+        let (lots, log) = self.draw(contested, still_needed);
+        winners.extend(lots.into_iter().map(|(seat, _)| seat));
+        (winners, log)
+    }
+
+    /// Draw `n` items out of `candidates`, recording each draw.
+    ///
+    /// Every draw hashes `seed || counter` (SHA-256, digest read as a
+    /// big-endian integer, reduced modulo the number of remaining
+    /// candidates), so the same seed always produces the same sequence of
+    /// picks on any platform.
+    pub fn draw<T>(&self, mut candidates: Vec<T>, n: Count) -> (Vec<T>, Vec<Draw>) {
+        let seed = self.seed();
+        let mut drawn = Vec::new();
+        let mut log = Vec::new();
+        let mut counter = 0u64;
+        while (drawn.len() as Count) < n && !candidates.is_empty() {
+            let index = draw_index(seed, counter, candidates.len());
+            log.push(Draw { counter, index });
+            drawn.push(candidates.remove(index));
+            counter += 1;
+        }
+        (drawn, log)
+    }
+}
+
+fn draw_index(seed: &str, counter: u64, remaining: usize) -> usize {
+    let mut hasher = Sha256::new();
+    hasher.update(seed.as_bytes());
+    hasher.update(counter.to_be_bytes());
+    let digest = hasher.finalize();
+    let remaining = remaining as u128;
+    let value = digest.iter().fold(0u128, |acc, byte| ((acc << 8) | *byte as u128) % remaining);
+    value as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countback_strict_winner_needs_no_lot() {
+        let tie_break = TieBreak::Countback { seed: "test".to_owned() };
+        let tied = vec![('A', Seats::filled(10)), ('B', Seats::filled(8))];
+        let (winners, log) = tie_break.resolve(tied, 1);
+        assert_eq!(winners, vec!['A']);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn countback_excludes_candidates_below_cutoff() {
+        let tie_break = TieBreak::Countback { seed: "test".to_owned() };
+        let tied = vec![
+            ('A', Seats::filled(10)),
+            ('B', Seats::filled(8)),
+            ('C', Seats::filled(8)),
+            ('D', Seats::filled(8)),
+            ('E', Seats::filled(5)),
+        ];
+        let (winners, log) = tie_break.resolve(tied, 3);
+        assert_eq!(winners.len(), 3);
+        assert!(winners.contains(&'A'));
+        assert!(!winners.contains(&'E'));
+        assert_eq!(log.len(), 1);
+    }
+
+    #[test]
+    fn countback_awards_exact_tie_without_drawing() {
+        let tie_break = TieBreak::Countback { seed: "test".to_owned() };
+        let tied = vec![('A', Seats::filled(8)), ('B', Seats::filled(8))];
+        let (winners, log) = tie_break.resolve(tied, 2);
+        assert_eq!(winners.len(), 2);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn lottery_always_draws_among_all_tied() {
+        let tie_break = TieBreak::Lottery { seed: "test".to_owned() };
+        let tied = vec![('A', Seats::filled(10)), ('B', Seats::filled(8))];
+        let (winners, log) = tie_break.resolve(tied, 1);
+        assert_eq!(winners.len(), 1);
+        assert_eq!(log.len(), 1);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{Count, Draw, Number, Quality};
+
+/// So `allocate_single_step` can report a party's quality without being
+/// generic over the exact-arithmetic backend `N` itself.
+pub trait Ratio {
+    fn as_ratio(&self) -> (Count, Count);
+}
+
+impl<N: Number> Ratio for Quality<N> {
+    fn as_ratio(&self) -> (Count, Count) {
+        Quality::as_ratio(self)
+    }
+}
+
+/// Which rule produced a [`Stage`] of the allocation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(any(feature = "serde", feature = "wasm"), derive(serde::Serialize))]
+pub enum Phase {
+    /// A whole seat, awarded by the largest-averages or largest-surplus rule.
+    FullSeat,
+    /// A "rest seat", awarded once no party could reach a whole quota.
+    RestSeat,
+    /// The absolute-majority correction.
+    MajorityCorrection,
+}
+
+/// One step of an allocation, recorded so the result can be audited
+/// independently of the program that produced it.
+#[derive(Clone, Debug)]
+#[cfg_attr(any(feature = "serde", feature = "wasm"), derive(serde::Serialize))]
+pub struct Stage {
+    pub phase: Phase,
+    /// Each party's quality this step, as an exact `numerator/denominator`
+    /// ratio; `None` if the party had no candidates left or did not qualify
+    /// under the rule in force. All `None` for the majority correction.
+    pub qualities: Vec<Option<(Count, Count)>>,
+    /// Indices of the parties awarded a seat this step.
+    pub winners: Vec<usize>,
+    /// Whether a drawing of lots was needed this step (for the majority
+    /// correction: to pick which party gives up a seat).
+    pub lot: bool,
+    /// Each party's running seat total after this step.
+    pub totals: Vec<Count>,
+}
+
+/// Everything gathered in the course of an allocation, besides the final
+/// seat counts themselves.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(any(feature = "serde", feature = "wasm"), derive(serde::Serialize))]
+pub struct AllocationLog {
+    pub lots: Vec<Draw>,
+    pub trace: Vec<Stage>,
+}
+
+impl AllocationLog {
+    pub(crate) fn extend(&mut self, other: AllocationLog) {
+        self.lots.extend(other.lots);
+        self.trace.extend(other.trace);
+    }
+}
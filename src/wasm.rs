@@ -0,0 +1,74 @@
+//! Browser entry points, so the apportionment engine can run client-side
+//! without the CLI.
+use crate::{allocate, allocate_national, allocate_per_average, allocate_per_surplus};
+use crate::{Count, DivisorMethod, Native, Seats, Stage, TieBreak, Votes};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// The outcome of a browser-side allocation: each party's final seat count,
+/// alongside the structured trace of how it was reached.
+#[derive(Serialize)]
+struct JsAllocation {
+    seats: Vec<Count>,
+    trace: Vec<Stage>,
+}
+
+fn js_votes(votes: Vec<u32>) -> Vec<Votes> {
+    votes.into_iter().map(|v| Votes(v as Count)).collect()
+}
+
+fn js_seats(candidates: Option<Vec<u32>>, parties: usize) -> Vec<Seats> {
+    match candidates {
+        Some(caps) => caps.into_iter().map(|c| Seats::limited(c as Count)).collect(),
+        None => vec![Seats::unlimited(); parties],
+    }
+}
+
+fn js_result(seats: Vec<Seats>, trace: Vec<Stage>) -> JsValue {
+    let result = JsAllocation { seats: seats.iter().map(Seats::count).collect(), trace };
+    serde_wasm_bindgen::to_value(&result).unwrap()
+}
+
+/// Run a largest-averages or largest-surplus allocation, picked by the
+/// `>= 19` seat threshold, same as [`allocate`].
+#[wasm_bindgen]
+pub fn allocate_js(total_seats: u32, votes: Vec<u32>, candidates: Option<Vec<u32>>, seed: String) -> JsValue {
+    let votes = js_votes(votes);
+    let mut seats = js_seats(candidates, votes.len());
+    let tie_break = TieBreak::Lottery { seed };
+    let log = allocate::<Native>(Seats::filled(total_seats as Count), votes, &mut seats, &tie_break);
+    js_result(seats, log.trace)
+}
+
+/// Run a national (Tweede Kamer) allocation, with the one-whole-seat voting
+/// threshold, same as [`allocate_national`].
+#[wasm_bindgen]
+pub fn allocate_national_js(total_seats: u32, votes: Vec<u32>, candidates: Option<Vec<u32>>, seed: String) -> JsValue {
+    let votes = js_votes(votes);
+    let mut seats = js_seats(candidates, votes.len());
+    let tie_break = TieBreak::Lottery { seed };
+    let log = allocate_national::<Native>(Seats::filled(total_seats as Count), votes, &mut seats, &tie_break);
+    js_result(seats, log.trace)
+}
+
+/// Force a largest-averages (D'Hondt) allocation regardless of seat count,
+/// same as [`allocate_per_average`].
+#[wasm_bindgen]
+pub fn allocate_per_average_js(total_seats: u32, votes: Vec<u32>, candidates: Option<Vec<u32>>, seed: String) -> JsValue {
+    let votes = js_votes(votes);
+    let mut seats = js_seats(candidates, votes.len());
+    let tie_break = TieBreak::Lottery { seed };
+    let log = allocate_per_average::<Native>(DivisorMethod::DHondt, Seats::filled(total_seats as Count), votes, &mut seats, &tie_break);
+    js_result(seats, log.trace)
+}
+
+/// Force a largest-surplus allocation regardless of seat count, same as
+/// [`allocate_per_surplus`].
+#[wasm_bindgen]
+pub fn allocate_per_surplus_js(total_seats: u32, votes: Vec<u32>, candidates: Option<Vec<u32>>, seed: String) -> JsValue {
+    let votes = js_votes(votes);
+    let mut seats = js_seats(candidates, votes.len());
+    let tie_break = TieBreak::Lottery { seed };
+    let log = allocate_per_surplus::<Native>(DivisorMethod::DHondt, Seats::filled(total_seats as Count), votes, &mut seats, &tie_break);
+    js_result(seats, log.trace)
+}